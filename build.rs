@@ -0,0 +1,32 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Compiles every shader under `shaders/` to SPIR-V in `OUT_DIR`, so
+/// `render::pipeline` can pull them in with `include_bytes!`.
+fn main() {
+    println!("cargo:rerun-if-changed=shaders");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let mut compiler = shaderc::Compiler::new().expect("failed to create shader compiler");
+
+    for entry in fs::read_dir("shaders").expect("failed to read shaders directory") {
+        let path = entry.expect("failed to read shader directory entry").path();
+
+        let kind = match path.extension().and_then(|e| e.to_str()) {
+            Some("vert") => shaderc::ShaderKind::Vertex,
+            Some("frag") => shaderc::ShaderKind::Fragment,
+            _ => continue,
+        };
+
+        let source = fs::read_to_string(&path).expect("failed to read shader source");
+        let file_name = path.file_name().unwrap().to_str().unwrap();
+
+        let binary = compiler
+            .compile_into_spirv(&source, kind, file_name, "main", None)
+            .expect("failed to compile shader");
+
+        let out_path = Path::new(&out_dir).join(format!("{}.spv", file_name));
+        fs::write(out_path, binary.as_binary_u8()).expect("failed to write compiled shader");
+    }
+}
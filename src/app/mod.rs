@@ -4,13 +4,18 @@ use anyhow::{anyhow, Result};
 
 use vulkanalia::loader::{LibloadingLoader, LIBRARY};
 use vulkanalia::prelude::v1_0::*;
-use vulkanalia::vk::{ExtDebugUtilsExtension, KhrSurfaceExtension};
+use vulkanalia::vk::{ExtDebugUtilsExtension, KhrSurfaceExtension, KhrSwapchainExtension};
 use vulkanalia::window as vk_window;
 
 use winit::window::Window;
 
+use crate::render::command;
 use crate::render::device;
+use crate::render::framebuffer;
 use crate::render::instance;
+use crate::render::pipeline;
+use crate::render::swapchain;
+use crate::render::sync;
 use crate::render::validation;
 
 #[derive(Clone, Debug)]
@@ -19,6 +24,14 @@ pub struct App {
     instance: Instance,
     data: AppData,
     device: Device,
+    // index into the per-frame-in-flight sync objects, advanced after every
+    // frame `render` submits (not when it skips or rebuilds the swapchain
+    // before submitting)
+    frame: usize,
+    // set by the windowing event loop on `WindowEvent::Resized`; `render`
+    // rebuilds the swapchain against the window's current size once it sees
+    // this set, then clears it
+    pub resized: bool,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -29,6 +42,11 @@ pub struct AppData {
     // so no further handling of this in App::destroy() required
     pub physical_device: vk::PhysicalDevice,
 
+    // the queue family indices of the physical device selected in
+    // `device::pick_physical_device`, reused so logical device creation does
+    // not need to re-query them
+    pub queue_family_indices: device::QueueFamilyIndices,
+
     // queues, which will be created along with logic device creation
     // queues are implicitly cleaned up, when the device is destroyed
     pub graphics_queue: vk::Queue,
@@ -36,6 +54,33 @@ pub struct AppData {
     // the presentation queue also needs to be created with the logic
     // device
     pub present_queue: vk::Queue,
+
+    // swapchain and its per-image resources
+    pub swapchain_format: vk::Format,
+    pub swapchain_extent: vk::Extent2D,
+    pub swapchain: vk::SwapchainKHR,
+    pub swapchain_images: Vec<vk::Image>,
+    pub swapchain_image_views: Vec<vk::ImageView>,
+
+    // fixed render pass and graphics pipeline used to draw the triangle
+    pub render_pass: vk::RenderPass,
+    pub pipeline_layout: vk::PipelineLayout,
+    pub pipeline: vk::Pipeline,
+
+    // one framebuffer per swapchain image view
+    pub framebuffers: Vec<vk::Framebuffer>,
+
+    // command pool (bound to the graphics queue family) and one pre-recorded
+    // command buffer per framebuffer
+    pub command_pool: vk::CommandPool,
+    pub command_buffers: Vec<vk::CommandBuffer>,
+
+    // per-frame-in-flight synchronization, plus a fence per swapchain image
+    // used to avoid reusing an image whose last frame is still rendering
+    pub image_available_semaphores: Vec<vk::Semaphore>,
+    pub render_finished_semaphores: Vec<vk::Semaphore>,
+    pub in_flight_fences: Vec<vk::Fence>,
+    pub images_in_flight: Vec<vk::Fence>,
 }
 
 // TODO: expose own safe wrapper around vulkan calls, which asserts the calling
@@ -52,27 +97,184 @@ impl App {
         // use the window and entry to create a vulkan instance
         let mut data = AppData::default();
         let instance = instance::create_instance(window, &entry, &mut data)?;
+        validation::create_debug_messenger(&instance, &mut data)?;
 
         // setup window surface
         data.surface = vk_window::create_surface(&instance, window)?;
 
         device::pick_physical_device(&instance, &mut data)?;
         let device = device::create_logical_device(&instance, &mut data)?;
+
+        swapchain::create_swapchain(window, &instance, &device, &mut data)?;
+        swapchain::create_swapchain_image_views(&device, &mut data)?;
+
+        pipeline::create_render_pass(&device, &mut data)?;
+        pipeline::create_pipeline(&device, &mut data)?;
+
+        framebuffer::create_framebuffers(&device, &mut data)?;
+
+        command::create_command_pool(&device, &mut data)?;
+        command::create_command_buffers(&device, &mut data)?;
+
+        sync::create_sync_objects(&device, &mut data)?;
+
         Ok(Self {
             entry,
             instance,
             data,
             device,
+            frame: 0,
+            resized: false,
         })
     }
 
     /// renders one frame
+    ///
+    /// Waits for the frame slot `self.frame` to become free, acquires the
+    /// next swapchain image, submits that image's pre-recorded command
+    /// buffer guarded by the frame's fence, and presents the result.
+    /// Rebuilds the swapchain whenever the window was resized or the
+    /// swapchain itself reports it is out of date/suboptimal.
     pub unsafe fn render(&mut self, window: &Window) -> Result<()> {
+        // a minimized window reports a zero-sized inner area, which is not a
+        // valid swapchain extent, so just skip the frame until it is restored
+        let size = window.inner_size();
+        if size.width == 0 || size.height == 0 {
+            return Ok(());
+        }
+
+        let in_flight_fence = self.data.in_flight_fences[self.frame];
+        self.device
+            .wait_for_fences(&[in_flight_fence], true, u64::MAX)?;
+
+        let result = self.device.acquire_next_image_khr(
+            self.data.swapchain,
+            u64::MAX,
+            self.data.image_available_semaphores[self.frame],
+            vk::Fence::null(),
+        );
+
+        let image_index = match result {
+            Ok((image_index, _)) => image_index as usize,
+            Err(vk::ErrorCode::OUT_OF_DATE_KHR) => {
+                self.resized = false;
+                return self.recreate_swapchain(window);
+            }
+            Err(e) => return Err(anyhow!(e)),
+        };
+
+        let image_in_flight = self.data.images_in_flight[image_index];
+        if !image_in_flight.is_null() {
+            self.device
+                .wait_for_fences(&[image_in_flight], true, u64::MAX)?;
+        }
+
+        self.data.images_in_flight[image_index] = in_flight_fence;
+
+        let wait_semaphores = &[self.data.image_available_semaphores[self.frame]];
+        let wait_stages = &[vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT];
+        let command_buffers = &[self.data.command_buffers[image_index]];
+        let signal_semaphores = &[self.data.render_finished_semaphores[self.frame]];
+        let submit_info = vk::SubmitInfo::builder()
+            .wait_semaphores(wait_semaphores)
+            .wait_dst_stage_mask(wait_stages)
+            .command_buffers(command_buffers)
+            .signal_semaphores(signal_semaphores);
+
+        self.device.reset_fences(&[in_flight_fence])?;
+        self.device
+            .queue_submit(self.data.graphics_queue, &[submit_info], in_flight_fence)?;
+
+        let swapchains = &[self.data.swapchain];
+        let image_indices = &[image_index as u32];
+        let present_info = vk::PresentInfoKHR::builder()
+            .wait_semaphores(signal_semaphores)
+            .swapchains(swapchains)
+            .image_indices(image_indices);
+
+        let present_result = self
+            .device
+            .queue_present_khr(self.data.present_queue, &present_info);
+        let changed = matches!(present_result, Ok(vk::SuccessCode::SUBOPTIMAL_KHR))
+            || matches!(present_result, Err(vk::ErrorCode::OUT_OF_DATE_KHR));
+
+        if self.resized || changed {
+            self.resized = false;
+            self.recreate_swapchain(window)?;
+        } else if let Err(e) = present_result {
+            return Err(anyhow!(e));
+        }
+
+        self.frame = (self.frame + 1) % sync::MAX_FRAMES_IN_FLIGHT;
+
         Ok(())
     }
 
+    /// rebuilds everything that depends on the swapchain's size against the
+    /// window's current inner size
+    unsafe fn recreate_swapchain(&mut self, window: &Window) -> Result<()> {
+        self.device.device_wait_idle()?;
+        self.destroy_swapchain();
+
+        swapchain::create_swapchain(window, &self.instance, &self.device, &mut self.data)?;
+        swapchain::create_swapchain_image_views(&self.device, &mut self.data)?;
+        pipeline::create_render_pass(&self.device, &mut self.data)?;
+        pipeline::create_pipeline(&self.device, &mut self.data)?;
+        framebuffer::create_framebuffers(&self.device, &mut self.data)?;
+        command::create_command_buffers(&self.device, &mut self.data)?;
+
+        self.data
+            .images_in_flight
+            .resize(self.data.swapchain_images.len(), vk::Fence::null());
+
+        Ok(())
+    }
+
+    /// destroys everything that depends on the swapchain's size, in reverse
+    /// order of creation, so it can be rebuilt by `recreate_swapchain`
+    unsafe fn destroy_swapchain(&mut self) {
+        self.device
+            .free_command_buffers(self.data.command_pool, &self.data.command_buffers);
+
+        self.data
+            .framebuffers
+            .iter()
+            .for_each(|f| self.device.destroy_framebuffer(*f, None));
+
+        self.device.destroy_pipeline(self.data.pipeline, None);
+        self.device
+            .destroy_pipeline_layout(self.data.pipeline_layout, None);
+        self.device.destroy_render_pass(self.data.render_pass, None);
+
+        self.data
+            .swapchain_image_views
+            .iter()
+            .for_each(|v| self.device.destroy_image_view(*v, None));
+        self.device.destroy_swapchain_khr(self.data.swapchain, None);
+    }
+
     /// destroy the app
     pub unsafe fn destroy(&mut self) {
+        // make sure nothing is still in flight before tearing anything down
+        self.device.device_wait_idle().unwrap();
+
+        self.destroy_swapchain();
+
+        self.data
+            .in_flight_fences
+            .iter()
+            .for_each(|f| self.device.destroy_fence(*f, None));
+        self.data
+            .render_finished_semaphores
+            .iter()
+            .for_each(|s| self.device.destroy_semaphore(*s, None));
+        self.data
+            .image_available_semaphores
+            .iter()
+            .for_each(|s| self.device.destroy_semaphore(*s, None));
+
+        self.device.destroy_command_pool(self.data.command_pool, None);
+
         // None is for allocation callbacks
         self.device.destroy_device(None);
 
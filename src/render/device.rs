@@ -0,0 +1,188 @@
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk::KhrSurfaceExtension;
+
+use crate::app::AppData;
+use crate::render::validation;
+
+/// Device extensions required of any physical device we pick.
+pub const DEVICE_EXTENSIONS: &[vk::ExtensionName] = &[vk::KHR_SWAPCHAIN_EXTENSION.name];
+
+/// The queue families required to drive rendering and presentation.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct QueueFamilyIndices {
+    pub graphics: u32,
+    pub present: u32,
+}
+
+impl QueueFamilyIndices {
+    unsafe fn get(
+        instance: &Instance,
+        data: &AppData,
+        physical_device: vk::PhysicalDevice,
+    ) -> Result<Self> {
+        let properties = instance.get_physical_device_queue_family_properties(physical_device);
+
+        let graphics = properties
+            .iter()
+            .position(|p| p.queue_flags.contains(vk::QueueFlags::GRAPHICS))
+            .map(|i| i as u32);
+
+        let mut present = None;
+        for (index, _) in properties.iter().enumerate() {
+            if instance.get_physical_device_surface_support_khr(
+                physical_device,
+                index as u32,
+                data.surface,
+            )? {
+                present = Some(index as u32);
+                break;
+            }
+        }
+
+        match (graphics, present) {
+            (Some(graphics), Some(present)) => Ok(Self { graphics, present }),
+            _ => Err(anyhow!("Missing required queue families.")),
+        }
+    }
+}
+
+unsafe fn check_physical_device(
+    instance: &Instance,
+    data: &AppData,
+    physical_device: vk::PhysicalDevice,
+) -> Result<()> {
+    QueueFamilyIndices::get(instance, data, physical_device)?;
+    check_physical_device_extensions(instance, physical_device)?;
+
+    let features = instance.get_physical_device_features(physical_device);
+    if features.geometry_shader != vk::TRUE {
+        return Err(anyhow!("Missing geometry shader support."));
+    }
+    if features.sampler_anisotropy != vk::TRUE {
+        return Err(anyhow!("Missing sampler anisotropy support."));
+    }
+
+    Ok(())
+}
+
+unsafe fn check_physical_device_extensions(
+    instance: &Instance,
+    physical_device: vk::PhysicalDevice,
+) -> Result<()> {
+    let extensions = instance
+        .enumerate_device_extension_properties(physical_device, None)?
+        .iter()
+        .map(|e| e.extension_name)
+        .collect::<HashSet<_>>();
+
+    if DEVICE_EXTENSIONS.iter().all(|e| extensions.contains(e)) {
+        Ok(())
+    } else {
+        Err(anyhow!("Missing required device extensions."))
+    }
+}
+
+/// Scores how suitable an already-qualified physical device is, favoring
+/// discrete GPUs and larger 2D image limits. Higher is better.
+unsafe fn score_physical_device(instance: &Instance, physical_device: vk::PhysicalDevice) -> u32 {
+    let properties = instance.get_physical_device_properties(physical_device);
+
+    let mut score = 0;
+    if properties.device_type == vk::PhysicalDeviceType::DISCRETE_GPU {
+        score += 1000;
+    }
+    score += properties.limits.max_image_dimension2_d;
+    score
+}
+
+/// Picks the highest-scoring physical device suitable for rendering and
+/// presentation.
+pub unsafe fn pick_physical_device(instance: &Instance, data: &mut AppData) -> Result<()> {
+    let mut best: Option<(u32, vk::PhysicalDevice, QueueFamilyIndices)> = None;
+
+    for physical_device in instance.enumerate_physical_devices()? {
+        let properties = instance.get_physical_device_properties(physical_device);
+
+        if let Err(error) = check_physical_device(instance, data, physical_device) {
+            log::warn!(
+                "Skipping physical device (`{}`): {}",
+                properties.device_name,
+                error
+            );
+            continue;
+        }
+
+        let indices = QueueFamilyIndices::get(instance, data, physical_device)?;
+        let score = score_physical_device(instance, physical_device);
+
+        if best.map_or(true, |(best_score, ..)| score > best_score) {
+            best = Some((score, physical_device, indices));
+        }
+    }
+
+    let (score, physical_device, indices) =
+        best.ok_or_else(|| anyhow!("Failed to find suitable physical device."))?;
+
+    let properties = instance.get_physical_device_properties(physical_device);
+    log::info!(
+        "Selected physical device (`{}`, score {}).",
+        properties.device_name,
+        score
+    );
+    data.physical_device = physical_device;
+    data.queue_family_indices = indices;
+
+    Ok(())
+}
+
+/// Creates the logical device and retrieves the graphics/present queues.
+pub unsafe fn create_logical_device(instance: &Instance, data: &mut AppData) -> Result<Device> {
+    let indices = data.queue_family_indices;
+
+    let mut unique_indices = HashSet::new();
+    unique_indices.insert(indices.graphics);
+    unique_indices.insert(indices.present);
+
+    let queue_priorities = &[1.0];
+    let queue_infos = unique_indices
+        .iter()
+        .map(|i| {
+            vk::DeviceQueueCreateInfo::builder()
+                .queue_family_index(*i)
+                .queue_priorities(queue_priorities)
+        })
+        .collect::<Vec<_>>();
+
+    // device-level layers are deprecated but some older loaders still expect
+    // them to be set, so keep this in sync with the instance layers
+    let layers = if validation::ENABLED {
+        vec![validation::VALIDATION_LAYER.as_ptr()]
+    } else {
+        vec![]
+    };
+
+    let extensions = DEVICE_EXTENSIONS
+        .iter()
+        .map(|n| n.as_ptr())
+        .collect::<Vec<_>>();
+
+    let features = vk::PhysicalDeviceFeatures::builder()
+        .geometry_shader(true)
+        .sampler_anisotropy(true);
+
+    let info = vk::DeviceCreateInfo::builder()
+        .queue_create_infos(&queue_infos)
+        .enabled_layer_names(&layers)
+        .enabled_extension_names(&extensions)
+        .enabled_features(&features);
+
+    let device = instance.create_device(data.physical_device, &info, None)?;
+
+    data.graphics_queue = device.get_device_queue(indices.graphics, 0);
+    data.present_queue = device.get_device_queue(indices.present, 0);
+
+    Ok(device)
+}
@@ -0,0 +1,26 @@
+use anyhow::Result;
+use vulkanalia::prelude::v1_0::*;
+
+use crate::app::AppData;
+
+/// Creates one framebuffer per swapchain image view, all sharing the render
+/// pass created in `render::pipeline::create_render_pass`.
+pub unsafe fn create_framebuffers(device: &Device, data: &mut AppData) -> Result<()> {
+    data.framebuffers = data
+        .swapchain_image_views
+        .iter()
+        .map(|v| {
+            let attachments = &[*v];
+            let info = vk::FramebufferCreateInfo::builder()
+                .render_pass(data.render_pass)
+                .attachments(attachments)
+                .width(data.swapchain_extent.width)
+                .height(data.swapchain_extent.height)
+                .layers(1);
+
+            device.create_framebuffer(&info, None)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use anyhow::Result;
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::window as vk_window;
+
+use winit::window::Window;
+
+use crate::app::AppData;
+use crate::render::validation;
+
+/// creates a new vulkan instance using entry.create_instance
+/// the window parameter is used to enumerate all required extensions
+///
+/// The 'Instance' returned by this function is not a raw vulkan instance
+/// (this would be vk::Instance), it is an abstraction created by vulkanalia,
+/// which combines the raw vulkan instance and the loaded commands for that instance
+pub unsafe fn create_instance(window: &Window, entry: &Entry, _data: &mut AppData) -> Result<Instance> {
+    // no strictly necessary
+    let application_info = vk::ApplicationInfo::builder()
+        .application_name(b"Vulkan Tutorial\0")
+        .application_version(vk::make_version(1, 0, 0))
+        .engine_name(b"No Engine\0")
+        .engine_version(vk::make_version(1, 0, 0))
+        .api_version(vk::make_version(1, 0, 0));
+
+    // the validation layer has to both be requested here and be available from
+    // the loader, otherwise we silently get no validation output
+    let layers = if validation::ENABLED {
+        validation::check_validation_layer_support(entry)?;
+        vec![validation::VALIDATION_LAYER.as_ptr()]
+    } else {
+        vec![]
+    };
+
+    // lots of information is passed to vulkan (and vulkanalia) by passing structs
+    // so for creating an instance, we need to fill in one more struct
+    //
+    // enumerate all globally required extensions for vk_window and convert them to
+    // null terminated c_strings (*const i8)
+    //
+    // globally means global for the whole program
+    let mut extensions = vk_window::get_required_instance_extensions(window)
+        .iter()
+        .map(|e| e.as_ptr())
+        .collect::<Vec<_>>();
+
+    // the debug messenger needs debug-utils to report on instance creation and
+    // destruction itself, so it is requested alongside the validation layer
+    if validation::ENABLED {
+        extensions.push(vk::EXT_DEBUG_UTILS_EXTENSION.name.as_ptr());
+    }
+
+    // on macOS, the Vulkan implementation is provided through MoltenVK, which is only
+    // a "portability" implementation and is not allowed to be used unless we opt in
+    // by requesting the portability enumeration extension and flag
+    let available_extensions = entry.enumerate_instance_extension_properties(None)?;
+    let flags = if available_extensions
+        .iter()
+        .any(|e| e.extension_name == vk::KHR_PORTABILITY_ENUMERATION_EXTENSION.name)
+    {
+        log::info!("enabling extensions for macOS portability");
+        extensions.push(vk::KHR_PORTABILITY_ENUMERATION_EXTENSION.name.as_ptr());
+        vk::InstanceCreateFlags::ENUMERATE_PORTABILITY_KHR
+    } else {
+        vk::InstanceCreateFlags::empty()
+    };
+
+    // chaining the debug messenger create info into the instance create info's
+    // `pNext` lets the validation layer report on instance creation/destruction
+    // itself, which otherwise falls outside the lifetime of the messenger
+    // created further down in `App::create`
+    let mut debug_info = validation::debug_messenger_create_info();
+
+    // create a vulkan instance (the connection between our program and the
+    // Vulkan library)
+    let mut info = vk::InstanceCreateInfo::builder()
+        .application_info(&application_info)
+        .enabled_layer_names(&layers)
+        .enabled_extension_names(&extensions)
+        .flags(flags);
+
+    if validation::ENABLED {
+        info = info.push_next(&mut debug_info);
+    }
+
+    Ok(entry.create_instance(&info, None)?)
+}
@@ -0,0 +1,8 @@
+pub mod command;
+pub mod device;
+pub mod framebuffer;
+pub mod instance;
+pub mod pipeline;
+pub mod swapchain;
+pub mod sync;
+pub mod validation;
@@ -0,0 +1,35 @@
+use anyhow::Result;
+use vulkanalia::prelude::v1_0::*;
+
+use crate::app::AppData;
+
+/// The number of frames that may be in flight (submitted but not yet
+/// finished rendering) at once.
+pub const MAX_FRAMES_IN_FLIGHT: usize = 2;
+
+/// Creates the per-frame-in-flight semaphores and fences used by the draw
+/// loop, plus a fence-per-swapchain-image slot to guard against reusing an
+/// image whose last frame is still rendering.
+pub unsafe fn create_sync_objects(device: &Device, data: &mut AppData) -> Result<()> {
+    let semaphore_info = vk::SemaphoreCreateInfo::builder();
+    // start signaled so the first `render` call does not block forever
+    // waiting on a frame that was never submitted
+    let fence_info = vk::FenceCreateInfo::builder().flags(vk::FenceCreateFlags::SIGNALED);
+
+    for _ in 0..MAX_FRAMES_IN_FLIGHT {
+        data.image_available_semaphores
+            .push(device.create_semaphore(&semaphore_info, None)?);
+        data.render_finished_semaphores
+            .push(device.create_semaphore(&semaphore_info, None)?);
+        data.in_flight_fences
+            .push(device.create_fence(&fence_info, None)?);
+    }
+
+    data.images_in_flight = data
+        .swapchain_images
+        .iter()
+        .map(|_| vk::Fence::null())
+        .collect();
+
+    Ok(())
+}
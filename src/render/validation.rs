@@ -0,0 +1,77 @@
+use std::collections::HashSet;
+use std::ffi::{c_void, CStr};
+
+use anyhow::{anyhow, Result};
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk::ExtDebugUtilsExtension;
+
+use crate::app::AppData;
+
+/// Whether validation layers should be enabled.
+///
+/// Validation layers add a non-trivial amount of overhead, so they are only
+/// turned on for debug builds.
+pub const ENABLED: bool = cfg!(debug_assertions);
+
+/// Name of the standard Khronos validation layer.
+pub const VALIDATION_LAYER: vk::ExtensionName =
+    vk::ExtensionName::from_bytes(b"VK_LAYER_KHRONOS_validation");
+
+/// Verifies that the standard validation layer is available from the loader.
+pub unsafe fn check_validation_layer_support(entry: &Entry) -> Result<()> {
+    let available_layers = entry
+        .enumerate_instance_layer_properties()?
+        .iter()
+        .map(|l| l.layer_name)
+        .collect::<HashSet<_>>();
+
+    if available_layers.contains(&VALIDATION_LAYER) {
+        Ok(())
+    } else {
+        Err(anyhow!("Validation layer requested but not supported."))
+    }
+}
+
+/// Builds the debug messenger create info.
+///
+/// Shared by the `pNext` chain used while creating/destroying the instance
+/// itself (see `render::instance::create_instance`) and the long-lived
+/// messenger created right after in `create_debug_messenger`.
+pub fn debug_messenger_create_info() -> vk::DebugUtilsMessengerCreateInfoEXTBuilder<'static> {
+    vk::DebugUtilsMessengerCreateInfoEXT::builder()
+        .message_severity(vk::DebugUtilsMessageSeverityFlagsEXT::all())
+        .message_type(vk::DebugUtilsMessageTypeFlagsEXT::all())
+        .user_callback(Some(debug_callback))
+}
+
+/// Creates the long-lived debug messenger and stores it in `AppData`.
+pub unsafe fn create_debug_messenger(instance: &Instance, data: &mut AppData) -> Result<()> {
+    if !ENABLED {
+        return Ok(());
+    }
+
+    let info = debug_messenger_create_info();
+    data.messenger = instance.create_debug_utils_messenger_ext(&info, None)?;
+    Ok(())
+}
+
+extern "system" fn debug_callback(
+    severity: vk::DebugUtilsMessageSeverityFlagsEXT,
+    type_: vk::DebugUtilsMessageTypeFlagsEXT,
+    data: *const vk::DebugUtilsMessengerCallbackDataEXT,
+    _: *mut c_void,
+) -> vk::Bool32 {
+    let message = unsafe { CStr::from_ptr((*data).message) }.to_string_lossy();
+
+    if severity == vk::DebugUtilsMessageSeverityFlagsEXT::ERROR {
+        log::error!("({:?}) {}", type_, message);
+    } else if severity == vk::DebugUtilsMessageSeverityFlagsEXT::WARNING {
+        log::warn!("({:?}) {}", type_, message);
+    } else if severity == vk::DebugUtilsMessageSeverityFlagsEXT::INFO {
+        log::debug!("({:?}) {}", type_, message);
+    } else {
+        log::trace!("({:?}) {}", type_, message);
+    }
+
+    vk::FALSE
+}